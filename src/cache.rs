@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::Hasher;
+use crate::link::preferred_hash;
+use crate::{Error, ErrorKind, Link};
+
+const FALLBACK_ALGO: &str = "sha256";
+
+/// Folded into temp-file names so two racing callers never collide on the same path.
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    algo: String,
+    digest: String,
+    size: u64,
+    filename: String,
+}
+
+/// A content-addressable store for downloaded artifacts, indexed by URL.
+#[derive(Debug)]
+pub struct Cache {
+    root: PathBuf,
+    index_lock: Mutex<()>,
+}
+
+impl Cache {
+    pub fn new(root: PathBuf) -> Result<Self, Error> {
+        fs::create_dir_all(root.join("content"))?;
+        Ok(Self { root, index_lock: Mutex::new(()) })
+    }
+
+    pub fn get(&self, link: &Link) -> Option<PathBuf> {
+        let index = {
+            let _guard = self.index_lock.lock().unwrap();
+            self.load_index()
+        };
+        let entry = index.get(&link.url_without_fragment())?;
+        if let Some(hashes) = link.hashes() {
+            match hashes.get(&entry.algo) {
+                Some(expected) if expected == &entry.digest => {}
+                _ => return None,
+            }
+        }
+        let path = self.content_path(&entry.algo, &entry.digest);
+        if verify_entry(&path, &entry.algo, &entry.digest) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    pub fn put(&self, link: &Link, bytes: &[u8]) -> Result<PathBuf, Error> {
+        let (algo, digest) = match link.hashes().and_then(|h| preferred_hash(&h)) {
+            Some(pair) => pair,
+            None => {
+                let mut hasher = Hasher::new(FALLBACK_ALGO).unwrap();
+                hasher.update(bytes);
+                (FALLBACK_ALGO.to_string(), hasher.hexdigest())
+            }
+        };
+        let path = self.content_path(&algo, &digest);
+        fs::create_dir_all(path.parent().unwrap())?;
+        // Write to a temp file, then rename atomically into place.
+        let tmp_path = path.with_file_name(format!(
+            "{}.tmp-{}-{}",
+            path.file_name().unwrap().to_string_lossy(),
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, &path)?;
+
+        {
+            let _guard = self.index_lock.lock().unwrap();
+            let mut index = self.load_index();
+            index.insert(
+                link.url_without_fragment(),
+                CacheEntry {
+                    algo,
+                    digest,
+                    size: bytes.len() as u64,
+                    filename: link.filename(),
+                },
+            );
+            self.save_index(&index)?;
+        }
+        Ok(path)
+    }
+
+    pub fn prune(&self) -> Result<usize, Error> {
+        let _guard = self.index_lock.lock().unwrap();
+        let mut index = self.load_index();
+        let before = index.len();
+        index.retain(|_, entry| verify_entry(&self.content_path(&entry.algo, &entry.digest), &entry.algo, &entry.digest));
+        let removed = before - index.len();
+        self.save_index(&index)?;
+        Ok(removed)
+    }
+
+    /// `<root>/content/<algo>/<aa>/<bb>/<full-hex>`.
+    fn content_path(&self, algo: &str, digest: &str) -> PathBuf {
+        let (aa, bb) = (&digest[..2.min(digest.len())], &digest[2.min(digest.len())..4.min(digest.len())]);
+        self.root.join("content").join(algo).join(aa).join(bb).join(digest)
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.root.join("index.json")
+    }
+
+    fn load_index(&self) -> HashMap<String, CacheEntry> {
+        fs::read(self.index_path())
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_index(&self, index: &HashMap<String, CacheEntry>) -> Result<(), Error> {
+        let data = serde_json::to_vec_pretty(index)
+            .map_err(|e| Error::new(ErrorKind::IOError, e.to_string()))?;
+        fs::write(self.index_path(), data)?;
+        Ok(())
+    }
+}
+
+fn verify_entry(path: &Path, algo: &str, digest: &str) -> bool {
+    let Ok(bytes) = fs::read(path) else {
+        return false;
+    };
+    let Some(mut hasher) = Hasher::new(algo) else {
+        return false;
+    };
+    hasher.update(&bytes);
+    hasher.hexdigest() == digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_link(url: &str, hashes: Option<HashMap<String, String>>) -> Link {
+        Link::new(url.to_string(), None, None, None, hashes, None).unwrap()
+    }
+
+    fn test_cache() -> (Cache, PathBuf) {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "unearth-cache-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        (Cache::new(dir.clone()).unwrap(), dir)
+    }
+
+    #[test]
+    fn test_put_and_get_roundtrip() {
+        let (cache, dir) = test_cache();
+        let link = test_link("https://example.com/foo-1.0.tar.gz", None);
+        let path = cache.put(&link, b"hello world").unwrap();
+        assert!(path.exists());
+        let cached = cache.get(&link).unwrap();
+        assert_eq!(cached, path);
+        assert_eq!(fs::read(cached).unwrap(), b"hello world");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_get_is_a_miss_when_declared_hash_disagrees() {
+        let (cache, dir) = test_cache();
+        let stored = test_link("https://example.com/foo-1.0.tar.gz", None);
+        cache.put(&stored, b"hello world").unwrap();
+
+        let mut wrong_hash = HashMap::new();
+        wrong_hash.insert("sha256".to_string(), "00".repeat(32));
+        let mismatched = test_link("https://example.com/foo-1.0.tar.gz", Some(wrong_hash));
+        assert!(cache.get(&mismatched).is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_get_is_a_miss_when_content_is_corrupt() {
+        let (cache, dir) = test_cache();
+        let link = test_link("https://example.com/foo-1.0.tar.gz", None);
+        let path = cache.put(&link, b"hello world").unwrap();
+        fs::write(&path, b"corrupted").unwrap();
+        assert!(cache.get(&link).is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_prune_removes_corrupt_entries_only() {
+        let (cache, dir) = test_cache();
+        let kept = test_link("https://example.com/kept-1.0.tar.gz", None);
+        let corrupted = test_link("https://example.com/corrupted-1.0.tar.gz", None);
+        cache.put(&kept, b"kept bytes").unwrap();
+        let corrupted_path = cache.put(&corrupted, b"corrupted bytes").unwrap();
+        fs::remove_file(&corrupted_path).unwrap();
+
+        assert_eq!(cache.prune().unwrap(), 1);
+        assert!(cache.get(&kept).is_some());
+        assert!(cache.get(&corrupted).is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_content_path_shards_two_levels_deep() {
+        let (cache, dir) = test_cache();
+        let digest = "0123456789abcdef";
+        let path = cache.content_path("sha256", digest);
+        assert_eq!(
+            path,
+            dir.join("content").join("sha256").join("01").join("23").join(digest)
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -5,7 +5,11 @@ use pep508_rs::VersionOrUrl;
 use pep_427::WheelName;
 
 use crate::{
-    hash, session::PyPISession, source::ARCHIVE_EXTENSIONS, Error, ErrorKind, Link, TargetPython,
+    hash,
+    link::{normalize_hash_map, preferred_hash, DistMetadata},
+    session::PyPISession,
+    source::ARCHIVE_EXTENSIONS,
+    Error, ErrorKind, Link, TargetPython,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -18,6 +22,12 @@ pub struct Package {
     link: Link,
 }
 
+impl Package {
+    pub fn link(&self) -> &Link {
+        &self.link
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct FormatControl {
     only_binary: bool,
@@ -173,11 +183,30 @@ impl Evaluator<'_> {
     }
 
     fn get_hash(&self, link: &mut Link, hash_name: &str) -> Result<String, Error> {
+        // A previous evaluation (or download) of the same URL may already
+        // have the artifact on disk under its content digest; reuse it
+        // instead of pulling the bytes over the network again.
+        if let Some(cache) = self.session.cache() {
+            if let Some(path) = cache.get(link) {
+                if let Ok(bytes) = std::fs::read(&path) {
+                    let mut hasher = hash::Hasher::new(hash_name).ok_or(Error::new(
+                        ErrorKind::LinkMismatchError,
+                        format!("Unsupported hash algo {}", hash_name),
+                    ))?;
+                    hasher.update(&bytes);
+                    let digest = hasher.hexdigest();
+                    record_hash(link, hash_name, &digest);
+                    return Ok(digest);
+                }
+            }
+        }
+
         let mut resp = self.session.get(&link.normalized).send()?;
         let mut hasher = hash::Hasher::new(hash_name).ok_or(Error::new(
             ErrorKind::LinkMismatchError,
             format!("Unsupported hash algo {}", hash_name),
         ))?;
+        let mut bytes = Vec::new();
         let mut buffer = [0; 1024 * 8];
         loop {
             let bytes_read = resp.read(&mut buffer)?;
@@ -185,19 +214,69 @@ impl Evaluator<'_> {
                 break;
             }
             hasher.update(&buffer[..bytes_read]);
+            bytes.extend_from_slice(&buffer[..bytes_read]);
         }
         let digest = hasher.hexdigest();
-        match link.hashes_map {
-            Some(ref mut hashes) => {
-                hashes.insert(hash_name.to_string(), digest.clone());
+        record_hash(link, hash_name, &digest);
+        if let Some(cache) = self.session.cache() {
+            cache.put(link, &bytes)?;
+        }
+        Ok(digest)
+    }
+
+    /// Fetch the standalone PEP 658 `<link>.metadata` file instead of the full distribution.
+    pub fn fetch_metadata(&self, link: &Link) -> Result<String, Error> {
+        let metadata_link = link.metadata_link().ok_or_else(|| {
+            Error::new(
+                ErrorKind::CollectError,
+                format!("{} does not advertise a PEP 658 metadata file", link),
+            )
+        })?;
+
+        let expected_hash = match &metadata_link.dist_metadata {
+            Some(DistMetadata::Hashes(hashes)) => preferred_hash(&normalize_hash_map(hashes)),
+            _ => None,
+        };
+
+        let mut resp = self.session.get(&metadata_link.normalized).send()?;
+        crate::source::check_for_status(&resp)?;
+        let mut hasher = expected_hash
+            .as_ref()
+            .map(|(algo, _)| {
+                hash::Hasher::new(algo).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::LinkMismatchError,
+                        format!("Unsupported hash algo {}", algo),
+                    )
+                })
+            })
+            .transpose()?;
+        let mut bytes = Vec::new();
+        let mut buffer = [0; 1024 * 8];
+        loop {
+            let bytes_read = resp.read(&mut buffer)?;
+            if bytes_read == 0 {
+                break;
             }
-            None => {
-                let mut hashes = HashMap::new();
-                hashes.insert(hash_name.to_string(), digest.clone());
-                link.hashes_map = Some(hashes);
+            if let Some(hasher) = hasher.as_mut() {
+                hasher.update(&buffer[..bytes_read]);
             }
+            bytes.extend_from_slice(&buffer[..bytes_read]);
         }
-        Ok(digest)
+
+        if let (Some(hasher), Some((algo, expected))) = (hasher, expected_hash) {
+            let actual = hasher.hexdigest();
+            if actual != expected {
+                return Err(hash_mismatch(&algo, &vec![expected], &actual));
+            }
+        }
+
+        String::from_utf8(bytes).map_err(|e| {
+            Error::new(
+                ErrorKind::CollectError,
+                format!("Invalid METADATA encoding for {}: {}", metadata_link, e),
+            )
+        })
     }
 
     fn check_hash(&self, link: &mut Link) -> Result<(), Error> {
@@ -224,6 +303,36 @@ impl Evaluator<'_> {
     }
 }
 
+fn record_hash(link: &mut Link, hash_name: &str, digest: &str) {
+    match link.hashes_map {
+        Some(ref mut hashes) => {
+            hashes.insert(hash_name.to_string(), digest.to_string());
+        }
+        None => {
+            let mut hashes = HashMap::new();
+            hashes.insert(hash_name.to_string(), digest.to_string());
+            link.hashes_map = Some(hashes);
+        }
+    }
+}
+
+impl Evaluator<'_> {
+    #[cfg(feature = "parallel")]
+    pub fn evaluate_links(&self, links: Vec<Link>) -> Vec<Result<Package, Error>> {
+        use rayon::prelude::*;
+
+        links
+            .into_par_iter()
+            .map(|link| self.evaluate_link(link))
+            .collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    pub fn evaluate_links(&self, links: Vec<Link>) -> Vec<Result<Package, Error>> {
+        links.into_iter().map(|link| self.evaluate_link(link)).collect()
+    }
+}
+
 fn hash_mismatch(hash_name: &str, expected: &Vec<String>, actual: &str) -> Error {
     Error::new(
         ErrorKind::LinkMismatchError,
@@ -313,3 +422,56 @@ pub fn evaluate_package(
 
     Ok(package)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluator_with_cached_session<'a>(session: &'a PyPISession, package_name: &'a str) -> Evaluator<'a> {
+        Evaluator {
+            package_name,
+            session,
+            format_control: FormatControl::new(false, false).unwrap(),
+            target_python: TargetPython::new(vec![]),
+            ignore_compatibility: true,
+            allow_yanked: true,
+            hashes: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_get_hash_reuses_the_download_cache() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "unearth-evaluator-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let session = PyPISession::new().with_cache(dir.clone()).unwrap();
+
+        let mut link = Link::new(
+            "https://example.com/foo-1.0.tar.gz".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        session.cache().unwrap().put(&link, b"wheel bytes").unwrap();
+
+        let mut expected_hasher = hash::Hasher::new("sha256").unwrap();
+        expected_hasher.update(b"wheel bytes");
+        let expected = expected_hasher.hexdigest();
+
+        let evaluator = evaluator_with_cached_session(&session, "foo");
+        // If this fell through to the network path instead of the cache,
+        // there's no server listening and the request would error out.
+        let digest = evaluator.get_hash(&mut link, "sha256").unwrap();
+        assert_eq!(digest, expected);
+        assert_eq!(link.hashes_map.as_ref().unwrap().get("sha256"), Some(&expected));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,3 +1,4 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use md5;
 use sha1;
 use sha1::Digest;
@@ -46,6 +47,19 @@ impl Hasher {
             Hasher::Sha512(h) => format!("{:x}", h.finalize()),
         }
     }
+
+    /// Standard (padded) base64 encoding of the raw digest bytes, as used by
+    /// npm-style Subresource Integrity strings (`"<algo>-<base64digest>"`).
+    pub fn b64digest(self) -> String {
+        match self {
+            Hasher::Md5(h) => STANDARD.encode(h.compute().0),
+            Hasher::Sha1(h) => STANDARD.encode(h.finalize()),
+            Hasher::Sha224(h) => STANDARD.encode(h.finalize()),
+            Hasher::Sha256(h) => STANDARD.encode(h.finalize()),
+            Hasher::Sha384(h) => STANDARD.encode(h.finalize()),
+            Hasher::Sha512(h) => STANDARD.encode(h.finalize()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -108,4 +122,21 @@ mod tests {
         let hasher = Hasher::new("invalid");
         assert!(hasher.is_none());
     }
+
+    #[test]
+    fn test_sha512_b64digest() {
+        let mut hasher = Hasher::new("sha512").unwrap();
+        hasher.update(b"hello");
+        assert_eq!(
+            hasher.b64digest(),
+            "m3HSJL1i83hdltRq0+o9czGb+8KJDKra4t/3JRlnPKcjI8PZm6XBHXx6zG4UuMXaDEZjR1wuXDre9G9zvN7AQw=="
+        );
+    }
+
+    #[test]
+    fn test_md5_b64digest() {
+        let mut hasher = Hasher::new("md5").unwrap();
+        hasher.update(b"hello");
+        assert_eq!(hasher.b64digest(), "XUFAKrxLKna5cZ2REBfFkg==");
+    }
 }
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::Hasher;
+use crate::{Error, ErrorKind};
+
+const KEY_ALGO: &str = "sha256";
+
+/// Matches pip's own `Cache-Control: max-age=0`: always revalidate.
+const DEFAULT_MAX_AGE: u64 = 0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexCacheEntry {
+    content_type: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    stored_at: u64,
+    max_age: u64,
+    body_file: String,
+}
+
+pub(crate) struct CachedResponse {
+    pub content_type: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: Vec<u8>,
+    pub fresh: bool,
+}
+
+/// An HTTP cache for simple-index responses, keyed by request URL.
+#[derive(Debug)]
+pub(crate) struct IndexCache {
+    root: PathBuf,
+    index_lock: Mutex<()>,
+}
+
+impl IndexCache {
+    pub fn new(root: PathBuf) -> Result<Self, Error> {
+        fs::create_dir_all(root.join("bodies"))?;
+        Ok(Self { root, index_lock: Mutex::new(()) })
+    }
+
+    pub fn get(&self, url: &str) -> Option<CachedResponse> {
+        let index = {
+            let _guard = self.index_lock.lock().unwrap();
+            self.load_index()
+        };
+        let entry = index.get(url)?;
+        let body = fs::read(self.root.join("bodies").join(&entry.body_file)).ok()?;
+        Some(CachedResponse {
+            content_type: entry.content_type.clone(),
+            etag: entry.etag.clone(),
+            last_modified: entry.last_modified.clone(),
+            body,
+            fresh: is_fresh(entry.stored_at, entry.max_age),
+        })
+    }
+
+    pub fn store(
+        &self,
+        url: &str,
+        content_type: &str,
+        etag: Option<String>,
+        last_modified: Option<String>,
+        max_age: Option<u64>,
+        body: &[u8],
+    ) -> Result<(), Error> {
+        let body_file = key_for(url);
+        fs::write(self.root.join("bodies").join(&body_file), body)?;
+
+        let _guard = self.index_lock.lock().unwrap();
+        let mut index = self.load_index();
+        index.insert(
+            url.to_string(),
+            IndexCacheEntry {
+                content_type: content_type.to_string(),
+                etag,
+                last_modified,
+                stored_at: now(),
+                max_age: max_age.unwrap_or(DEFAULT_MAX_AGE),
+                body_file,
+            },
+        );
+        self.save_index(&index)
+    }
+
+    pub fn touch(&self, url: &str) -> Result<(), Error> {
+        let _guard = self.index_lock.lock().unwrap();
+        let mut index = self.load_index();
+        if let Some(entry) = index.get_mut(url) {
+            entry.stored_at = now();
+            self.save_index(&index)?;
+        }
+        Ok(())
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.root.join("index.json")
+    }
+
+    fn load_index(&self) -> HashMap<String, IndexCacheEntry> {
+        fs::read(self.index_path())
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_index(&self, index: &HashMap<String, IndexCacheEntry>) -> Result<(), Error> {
+        let data = serde_json::to_vec_pretty(index)
+            .map_err(|e| Error::new(ErrorKind::IOError, e.to_string()))?;
+        fs::write(self.index_path(), data)?;
+        Ok(())
+    }
+}
+
+fn key_for(url: &str) -> String {
+    let mut hasher = Hasher::new(KEY_ALGO).unwrap();
+    hasher.update(url.as_bytes());
+    hasher.hexdigest()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn is_fresh(stored_at: u64, max_age: u64) -> bool {
+    now().saturating_sub(stored_at) < max_age
+}
+
+pub(crate) fn parse_max_age(header: &str) -> Option<u64> {
+    header.split(',').find_map(|directive| {
+        let (name, value) = directive.trim().split_once('=')?;
+        if name.eq_ignore_ascii_case("max-age") {
+            value.trim().parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_max_age() {
+        assert_eq!(parse_max_age("public, max-age=300"), Some(300));
+        assert_eq!(parse_max_age("max-age=0"), Some(0));
+        assert_eq!(parse_max_age("no-cache"), None);
+    }
+
+    #[test]
+    fn test_store_and_get_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("unearth-http-cache-test-{}", std::process::id()));
+        let cache = IndexCache::new(dir.clone()).unwrap();
+        cache
+            .store(
+                "https://pypi.org/simple/foo/",
+                "application/vnd.pypi.simple.v1+json",
+                Some("\"abc123\"".to_string()),
+                None,
+                Some(300),
+                b"{\"files\": []}",
+            )
+            .unwrap();
+        let cached = cache.get("https://pypi.org/simple/foo/").unwrap();
+        assert_eq!(cached.body, b"{\"files\": []}");
+        assert_eq!(cached.etag.as_deref(), Some("\"abc123\""));
+        assert!(cached.fresh);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -3,17 +3,21 @@ use pyo3::prelude::*;
 #[cfg(feature = "pyo3")]
 use pyo3_log;
 
+pub mod cache;
 pub mod error;
 pub mod evaluator;
 mod hash;
+mod http_cache;
 pub mod link;
+mod manylinux;
+mod musllinux;
 pub mod py;
 pub mod session;
 pub mod source;
 
 pub use error::{Error, ErrorKind};
 pub use link::Link;
-pub use py::{Tag, TargetPython};
+pub use py::{Arch, Os, Tag, TargetPython};
 
 /// A Python module implemented in Rust.
 #[cfg(feature = "pyo3")]
@@ -25,5 +29,7 @@ fn unearth(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<Link>()?;
     m.add_class::<TargetPython>()?;
     m.add_class::<Tag>()?;
+    m.add_class::<Os>()?;
+    m.add_class::<Arch>()?;
     Ok(())
 }
@@ -18,11 +18,59 @@ use url::Url;
 
 use crate::error::{Error, ErrorKind};
 
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+
 lazy_static! {
     static ref VCS_SCHEMES: [&'static str; 4] = ["git", "hg", "svn", "bzr"];
     static ref SUPPORTED_HASHES: [&'static str; 6] =
         ["sha1", "sha224", "sha384", "sha256", "sha512", "md5"];
     static ref SSH_GIT_URL: Regex = Regex::new(r"(^.+?://(?:.+?@)?.+?)(:)(.+$)").unwrap();
+    // npm-style Subresource Integrity string, e.g. `sha512-ABC...==`.
+    static ref SRI_ENTRY: Regex =
+        Regex::new(r"(?i)(sha512|sha384|sha256|sha224|sha1|md5)-([A-Za-z0-9+/]+=*)").unwrap();
+}
+
+const HASH_STRENGTH: [&str; 6] = ["sha512", "sha384", "sha256", "sha224", "sha1", "md5"];
+
+fn is_hex(s: &str) -> bool {
+    !s.is_empty() && s.len() % 2 == 0 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn parse_sri_hashes(value: &str) -> HashMap<String, String> {
+    SRI_ENTRY
+        .captures_iter(value)
+        .filter_map(|caps| {
+            let algo = caps[1].to_lowercase();
+            let digest = STANDARD.decode(&caps[2]).ok()?;
+            Some((algo, to_hex(&digest)))
+        })
+        .collect()
+}
+
+pub(crate) fn normalize_hash_map(raw: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut normalized = HashMap::new();
+    for (key, value) in raw {
+        let lower_key = key.to_lowercase();
+        if SUPPORTED_HASHES.contains(&lower_key.as_str()) && is_hex(value) {
+            normalized.insert(lower_key, value.to_lowercase());
+        } else {
+            normalized.extend(parse_sri_hashes(value));
+            normalized.extend(parse_sri_hashes(key));
+        }
+    }
+    normalized
+}
+
+pub fn preferred_hash(hashes: &HashMap<String, String>) -> Option<(String, String)> {
+    HASH_STRENGTH.iter().find_map(|algo| {
+        hashes
+            .get(*algo)
+            .map(|digest| (algo.to_string(), digest.clone()))
+    })
 }
 
 #[cfg_attr(feature = "pyo3", derive(FromPyObject))]
@@ -61,6 +109,7 @@ pub struct Link {
     pub requires_python: Option<String>,
     pub hashes_map: Option<HashMap<String, String>>,
     pub dist_metadata: Option<DistMetadata>,
+    pub filename_override: Option<String>,
 }
 
 /// Add ssh:// to git+ URLs if they don't already have it
@@ -112,9 +161,15 @@ impl Link {
             requires_python,
             hashes_map: hashes,
             dist_metadata,
+            filename_override: None,
         })
     }
 
+    pub fn with_filename_override(mut self, filename: Option<String>) -> Self {
+        self.filename_override = filename;
+        self
+    }
+
     pub fn is_file(&self) -> bool {
         self.parsed.scheme() == "file"
     }
@@ -136,6 +191,9 @@ impl Link {
         }
     }
     pub fn filename(&self) -> String {
+        if let Some(filename) = &self.filename_override {
+            return filename.clone();
+        }
         let path = self.parsed.path();
         let decoded_path = percent_encoding::percent_decode(path.as_bytes())
             .decode_utf8_lossy()
@@ -159,20 +217,25 @@ impl Link {
 
     pub fn hashes(&self) -> Option<HashMap<String, String>> {
         if let Some(hashes) = &self.hashes_map {
-            Some(hashes.clone())
-        } else {
-            let fragments = self.parsed.fragment()?;
-            let query = url::form_urlencoded::parse(fragments.as_bytes());
-            let hashes = query
-                .into_iter()
-                .filter(|(key, _)| SUPPORTED_HASHES.contains(&key.as_ref()))
-                .map(|(key, value)| (key.to_string(), value.to_string()))
-                .collect::<HashMap<_, _>>();
-            if hashes.is_empty() {
+            let normalized = normalize_hash_map(hashes);
+            return if normalized.is_empty() {
                 None
             } else {
-                Some(hashes)
-            }
+                Some(normalized)
+            };
+        }
+        let fragments = self.parsed.fragment()?;
+        let query = url::form_urlencoded::parse(fragments.as_bytes());
+        let mut hashes = query
+            .into_iter()
+            .filter(|(key, _)| SUPPORTED_HASHES.contains(&key.as_ref()))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        hashes.extend(parse_sri_hashes(fragments));
+        if hashes.is_empty() {
+            None
+        } else {
+            Some(hashes)
         }
     }
 
@@ -185,6 +248,22 @@ impl Link {
             .map(|(_, value)| value.to_string());
         egg
     }
+
+    /// The PEP 658 `<url>.metadata` sibling resource for this link, if advertised.
+    pub fn metadata_link(&self) -> Option<Self> {
+        match &self.dist_metadata {
+            Some(DistMetadata::Enabled(true)) | Some(DistMetadata::Hashes(_)) => Self::new(
+                format!("{}.metadata", self.url_without_fragment()),
+                self.comes_from.clone(),
+                None,
+                None,
+                None,
+                self.dist_metadata.clone(),
+            )
+            .ok(),
+            _ => None,
+        }
+    }
 }
 
 impl FromStr for Link {
@@ -343,6 +422,7 @@ impl Link {
             requires_python: None,
             hashes_map: None,
             dist_metadata: None,
+            filename_override: None,
         })
     }
 
@@ -385,20 +465,7 @@ impl Link {
 
     #[getter]
     fn dist_metadata_link(&self) -> Option<Self> {
-        match self.dist_metadata {
-            Some(DistMetadata::Enabled(true)) | Some(DistMetadata::Hashes(_)) => Some(
-                Self::py_new(
-                    format!("{}.metadata", self.url_without_fragment()),
-                    self.comes_from.clone(),
-                    None,
-                    None,
-                    None,
-                    None,
-                )
-                .unwrap(),
-            ),
-            _ => None,
-        }
+        self.metadata_link()
     }
 }
 
@@ -456,4 +523,55 @@ mod tests {
             PathBuf::from_str("/path/to/file").unwrap()
         );
     }
+
+    #[test]
+    fn test_hashes_from_sri_in_hashes_map() {
+        let mut hashes_map = HashMap::new();
+        hashes_map.insert(
+            "integrity".to_string(),
+            "sha256-LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=".to_string(),
+        );
+        let link = Link::new(
+            "https://example.com/foo-1.0-py3-none-any.whl".to_string(),
+            None,
+            None,
+            None,
+            Some(hashes_map),
+            None,
+        )
+        .unwrap();
+        let hashes = link.hashes().unwrap();
+        assert_eq!(
+            hashes.get("sha256").unwrap(),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+
+    #[test]
+    fn test_hashes_from_sri_in_fragment() {
+        let link = Link::new(
+            "https://example.com/foo-1.0-py3-none-any.whl#sha256-LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let hashes = link.hashes().unwrap();
+        assert_eq!(
+            hashes.get("sha256").unwrap(),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+
+    #[test]
+    fn test_preferred_hash_picks_strongest() {
+        let mut hashes = HashMap::new();
+        hashes.insert("md5".to_string(), "deadbeef".to_string());
+        hashes.insert("sha256".to_string(), "cafebabe".to_string());
+        let (algo, digest) = preferred_hash(&hashes).unwrap();
+        assert_eq!(algo, "sha256");
+        assert_eq!(digest, "cafebabe");
+    }
 }
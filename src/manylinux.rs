@@ -0,0 +1,109 @@
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+// `dlsym` for `gnu_get_libc_version` at runtime rather than linking against
+// it directly, which would fail to link on a musl host.
+pub fn host_glibc_version() -> Option<(u32, u32)> {
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+    #[cfg(target_os = "linux")]
+    {
+        type GnuGetLibcVersion = unsafe extern "C" fn() -> *const c_char;
+        unsafe {
+            let symbol = libc::dlsym(
+                libc::RTLD_DEFAULT,
+                b"gnu_get_libc_version\0".as_ptr() as *const c_char,
+            );
+            if symbol.is_null() {
+                return None;
+            }
+            let func: GnuGetLibcVersion = std::mem::transmute(symbol);
+            let ptr = func();
+            if ptr.is_null() {
+                return None;
+            }
+            parse_glibc_version(CStr::from_ptr(ptr).to_str().ok()?)
+        }
+    }
+}
+
+fn parse_glibc_version(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version.trim().split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+fn parse_perennial_tag(tag: &str) -> Option<(u32, u32, &str)> {
+    let rest = tag.strip_prefix("manylinux_")?;
+    let mut parts = rest.splitn(3, '_');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let arch = parts.next()?;
+    Some((major, minor, arch))
+}
+
+// Expands down to the PEP 600 floor (`m = 17`), plus the legacy
+// manylinux2014/2010/1 aliases on the arches they were defined for.
+pub fn expand_platform_tag(tag: &str) -> Vec<String> {
+    match parse_perennial_tag(tag) {
+        Some((2, minor, arch)) if minor >= 17 => {
+            let mut tags: Vec<String> = (17..=minor)
+                .rev()
+                .map(|m| format!("manylinux_2_{}_{}", m, arch))
+                .collect();
+            if arch == "i686" || arch == "x86_64" {
+                tags.push(format!("manylinux2014_{}", arch));
+                tags.push(format!("manylinux2010_{}", arch));
+                tags.push(format!("manylinux1_{}", arch));
+            }
+            tags
+        }
+        _ => vec![tag.to_string()],
+    }
+}
+
+// Unlike `expand_platform_tag`, caps at the glibc version actually installed.
+pub fn default_platform_tags(arch: &str) -> Vec<String> {
+    match host_glibc_version() {
+        Some((2, minor)) if minor >= 17 => expand_platform_tag(&format!("manylinux_2_{}_{}", minor, arch)),
+        Some((2, minor)) if arch == "i686" || arch == "x86_64" => {
+            let mut tags = Vec::new();
+            if minor >= 12 {
+                tags.push(format!("manylinux2010_{}", arch));
+            }
+            if minor >= 5 {
+                tags.push(format!("manylinux1_{}", arch));
+            }
+            tags
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_platform_tag_includes_legacy_aliases() {
+        let tags = expand_platform_tag("manylinux_2_28_x86_64");
+        assert!(tags.contains(&"manylinux_2_28_x86_64".to_string()));
+        assert!(tags.contains(&"manylinux_2_17_x86_64".to_string()));
+        assert!(tags.contains(&"manylinux2014_x86_64".to_string()));
+        assert!(tags.contains(&"manylinux2010_x86_64".to_string()));
+        assert!(tags.contains(&"manylinux1_x86_64".to_string()));
+        assert!(!tags.contains(&"manylinux_2_16_x86_64".to_string()));
+    }
+
+    #[test]
+    fn test_expand_platform_tag_skips_legacy_aliases_on_other_arches() {
+        let tags = expand_platform_tag("manylinux_2_28_aarch64");
+        assert!(!tags.iter().any(|t| t.starts_with("manylinux2014")));
+    }
+
+    #[test]
+    fn test_expand_platform_tag_passes_through_unrecognized_tags() {
+        assert_eq!(expand_platform_tag("linux_x86_64"), vec!["linux_x86_64".to_string()]);
+    }
+}
@@ -0,0 +1,134 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+const PT_INTERP: u32 = 3;
+
+lazy_static! {
+    static ref MUSL_VERSION: Regex = Regex::new(r"Version (\d+)\.(\d+)").unwrap();
+}
+
+// Reads the ELF `PT_INTERP` program header to recover the dynamic loader
+// a binary was linked against, without spawning a process.
+fn read_interp(path: &Path) -> Option<String> {
+    let data = fs::read(path).ok()?;
+    if data.len() < 64 || &data[0..4] != b"\x7fELF" {
+        return None;
+    }
+    let is_64 = match data[4] {
+        1 => false,
+        2 => true,
+        _ => return None,
+    };
+    let little_endian = match data[5] {
+        1 => true,
+        2 => false,
+        _ => return None,
+    };
+
+    let (phoff, phentsize, phnum) = if is_64 {
+        (
+            read_uint(data.get(32..40)?, little_endian),
+            read_uint(data.get(54..56)?, little_endian) as u16,
+            read_uint(data.get(56..58)?, little_endian) as u16,
+        )
+    } else {
+        (
+            read_uint(data.get(28..32)?, little_endian),
+            read_uint(data.get(42..44)?, little_endian) as u16,
+            read_uint(data.get(44..46)?, little_endian) as u16,
+        )
+    };
+
+    for i in 0..phnum {
+        let start = phoff as usize + i as usize * phentsize as usize;
+        let header = data.get(start..start + phentsize as usize)?;
+        let p_type = read_uint(header.get(0..4)?, little_endian) as u32;
+        if p_type != PT_INTERP {
+            continue;
+        }
+        let (p_offset, p_filesz) = if is_64 {
+            (
+                read_uint(header.get(8..16)?, little_endian),
+                read_uint(header.get(32..40)?, little_endian),
+            )
+        } else {
+            (
+                read_uint(header.get(4..8)?, little_endian),
+                read_uint(header.get(16..20)?, little_endian),
+            )
+        };
+        let segment = data.get(p_offset as usize..(p_offset + p_filesz) as usize)?;
+        let end = segment.iter().position(|&b| b == 0).unwrap_or(segment.len());
+        return std::str::from_utf8(&segment[..end]).ok().map(String::from);
+    }
+    None
+}
+
+fn read_uint(bytes: &[u8], little_endian: bool) -> u64 {
+    let mut buf = [0u8; 8];
+    if little_endian {
+        buf[..bytes.len()].copy_from_slice(bytes);
+        u64::from_le_bytes(buf)
+    } else {
+        buf[8 - bytes.len()..].copy_from_slice(bytes);
+        u64::from_be_bytes(buf)
+    }
+}
+
+// musl's loader prints a `Version x.y` line to stderr when run with no args.
+pub fn detect_musl_version(interpreter_path: &Path) -> Option<(u32, u32)> {
+    let interp = read_interp(interpreter_path)?;
+    if !interp.contains("ld-musl") {
+        return None;
+    }
+    let output = Command::new(&interp).output().ok()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let captures = MUSL_VERSION.captures(&stderr)?;
+    Some((captures[1].parse().ok()?, captures[2].parse().ok()?))
+}
+
+// Ignores the declared minor in `tag` in favor of the host's actual musl version.
+pub fn expand_platform_tag(tag: &str, interpreter_path: &Path) -> Vec<String> {
+    let Some(arch) = tag
+        .strip_prefix("musllinux_1_")
+        .and_then(|rest| rest.split_once('_'))
+        .map(|(_, arch)| arch)
+    else {
+        return vec![tag.to_string()];
+    };
+
+    match detect_musl_version(interpreter_path) {
+        Some((_, minor)) => (0..=minor)
+            .rev()
+            .map(|m| format!("musllinux_1_{}_{}", m, arch))
+            .collect(),
+        None => vec![tag.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_platform_tag_falls_back_without_a_real_interpreter() {
+        let missing = Path::new("/nonexistent/ld-musl-probe");
+        assert_eq!(
+            expand_platform_tag("musllinux_1_2_x86_64", missing),
+            vec!["musllinux_1_2_x86_64".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_expand_platform_tag_passes_through_unrecognized_tags() {
+        let missing = Path::new("/nonexistent/ld-musl-probe");
+        assert_eq!(
+            expand_platform_tag("linux_x86_64", missing),
+            vec!["linux_x86_64".to_string()]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::manylinux;
 #[cfg(feature = "pyo3")]
 use pyo3::{exceptions::PyNotImplementedError, prelude::*, pyclass::CompareOp, types::PyIterator};
 
@@ -14,10 +15,187 @@ impl IntoPy<PyObject> for PythonVersion {
 }
 
 impl PythonVersion {
+    pub fn new(major: u16, minor: u16) -> Self {
+        Self(major, minor)
+    }
+
     pub fn short_version(&self) -> String {
         format!("{}{}", self.0, self.1)
     }
 }
+
+pub mod native {
+    use super::{PythonVersion, Tag};
+
+    pub fn cpython_tags(py_ver: PythonVersion, abis: Option<&[String]>, platforms: &[String]) -> Vec<Tag> {
+        let (major, minor) = (py_ver.0, py_ver.1);
+        let interpreter = format!("cp{}{}", major, minor);
+        let default_abis = || vec![interpreter.clone(), "abi3".to_string(), "none".to_string()];
+        let abis: Vec<String> = abis.map(|a| a.to_vec()).unwrap_or_else(default_abis);
+
+        let mut tags = Vec::new();
+        for abi in &abis {
+            for platform in platforms {
+                tags.push(Tag {
+                    interpreter: interpreter.clone(),
+                    abi: abi.clone(),
+                    platform: platform.clone(),
+                });
+            }
+        }
+        if abis.iter().any(|abi| abi == "abi3") {
+            for m in (2..minor).rev() {
+                let older_interpreter = format!("cp{}{}", major, m);
+                for platform in platforms {
+                    tags.push(Tag {
+                        interpreter: older_interpreter.clone(),
+                        abi: "abi3".to_string(),
+                        platform: platform.clone(),
+                    });
+                }
+            }
+        }
+        tags
+    }
+
+    pub fn generic_tags(interpreter: &str, abis: Option<&[String]>, platforms: &[String]) -> Vec<Tag> {
+        let abis: Vec<String> = abis
+            .map(|a| a.to_vec())
+            .unwrap_or_else(|| vec!["none".to_string()]);
+        let mut tags = Vec::new();
+        for abi in &abis {
+            for platform in platforms {
+                tags.push(Tag {
+                    interpreter: interpreter.to_string(),
+                    abi: abi.clone(),
+                    platform: platform.clone(),
+                });
+            }
+        }
+        tags
+    }
+
+    pub fn compatible_tags(py_ver: PythonVersion, interpreter: &str, platforms: &[String]) -> Vec<Tag> {
+        let (major, minor) = (py_ver.0, py_ver.1);
+        let mut tags = Vec::new();
+        for platform in platforms {
+            tags.push(Tag {
+                interpreter: interpreter.to_string(),
+                abi: "none".to_string(),
+                platform: platform.clone(),
+            });
+        }
+
+        let mut any_platforms = platforms.to_vec();
+        any_platforms.push("any".to_string());
+
+        let mut generic_interpreters = vec![format!("py{}{}", major, minor), format!("py{}", major)];
+        generic_interpreters.extend((0..minor).rev().map(|m| format!("py{}{}", major, m)));
+
+        for interp in generic_interpreters {
+            for platform in &any_platforms {
+                tags.push(Tag {
+                    interpreter: interp.clone(),
+                    abi: "none".to_string(),
+                    platform: platform.clone(),
+                });
+            }
+        }
+        tags
+    }
+
+    pub fn get_supported_tags(
+        py_ver: PythonVersion,
+        abis: Option<Vec<String>>,
+        implementation: Option<&str>,
+        platforms: Vec<String>,
+    ) -> Vec<Tag> {
+        let implementation = implementation.unwrap_or("cp");
+        let interpreter = format!("{}{}", implementation, py_ver.short_version());
+        let mut tags = if implementation == "cp" {
+            cpython_tags(py_ver, abis.as_deref(), &platforms)
+        } else {
+            generic_tags(&interpreter, abis.as_deref(), &platforms)
+        };
+        tags.extend(compatible_tags(py_ver, &interpreter, &platforms));
+        tags
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn platforms(values: &[&str]) -> Vec<String> {
+            values.iter().map(|v| v.to_string()).collect()
+        }
+
+        #[test]
+        fn test_cpython_tags_pairs_every_abi_and_platform() {
+            let py_ver = PythonVersion::new(3, 9);
+            let abis = vec!["cp39".to_string(), "none".to_string()];
+            let tags = cpython_tags(py_ver, Some(&abis), &platforms(&["linux_x86_64", "manylinux_2_17_x86_64"]));
+            assert_eq!(
+                tags,
+                vec![
+                    Tag {
+                        interpreter: "cp39".to_string(),
+                        abi: "cp39".to_string(),
+                        platform: "linux_x86_64".to_string(),
+                    },
+                    Tag {
+                        interpreter: "cp39".to_string(),
+                        abi: "cp39".to_string(),
+                        platform: "manylinux_2_17_x86_64".to_string(),
+                    },
+                    Tag {
+                        interpreter: "cp39".to_string(),
+                        abi: "none".to_string(),
+                        platform: "linux_x86_64".to_string(),
+                    },
+                    Tag {
+                        interpreter: "cp39".to_string(),
+                        abi: "none".to_string(),
+                        platform: "manylinux_2_17_x86_64".to_string(),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn test_cpython_tags_appends_abi3_stability_tags_down_to_2() {
+            let py_ver = PythonVersion::new(3, 4);
+            let abis = vec!["abi3".to_string()];
+            let tags = cpython_tags(py_ver, Some(&abis), &platforms(&["linux_x86_64"]));
+            let stability_interpreters: Vec<&str> =
+                tags.iter().skip(1).map(|t| t.interpreter.as_str()).collect();
+            assert_eq!(stability_interpreters, vec!["cp33", "cp32"]);
+        }
+
+        #[test]
+        fn test_compatible_tags_includes_any_platform_and_older_minors() {
+            let py_ver = PythonVersion::new(3, 2);
+            let tags = compatible_tags(py_ver, "cp32", &platforms(&["linux_x86_64"]));
+            assert!(tags.iter().any(|t| t.platform == "any" && t.interpreter == "py3"));
+            assert!(tags
+                .iter()
+                .any(|t| t.interpreter == "py31" && t.platform == "any"));
+            assert!(tags
+                .iter()
+                .any(|t| t.interpreter == "py30" && t.platform == "any"));
+            assert!(!tags.iter().any(|t| t.interpreter == "py3-1"));
+        }
+
+        #[test]
+        fn test_get_supported_tags_dispatches_generic_for_non_cpython() {
+            let py_ver = PythonVersion::new(3, 9);
+            let tags = get_supported_tags(py_ver, None, Some("pp"), platforms(&["linux_x86_64"]));
+            assert!(tags
+                .iter()
+                .any(|t| t.interpreter == "pp39" && t.abi == "none"));
+            assert!(!tags.iter().any(|t| t.interpreter.starts_with("cp")));
+        }
+    }
+}
 #[cfg_attr(feature = "pyo3", pyclass(get_all))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Tag {
@@ -77,6 +255,106 @@ impl Tag {
     }
 }
 
+#[allow(non_camel_case_types)]
+#[cfg_attr(feature = "pyo3", pyclass(eq, eq_int))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Os {
+    Linux,
+    Macos,
+    Windows,
+    FreeBsd,
+}
+
+#[allow(non_camel_case_types)]
+#[cfg_attr(feature = "pyo3", pyclass(eq, eq_int))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Arch {
+    X86,
+    X86_64,
+    Aarch64,
+    Armv7L,
+    Ppc64,
+    Ppc64Le,
+    S390X,
+}
+
+const LATEST_MANYLINUX_MINOR: u32 = 39;
+const LATEST_MUSLLINUX_MINOR: u32 = 2;
+
+fn linux_arch_str(arch: Arch) -> Option<&'static str> {
+    Some(match arch {
+        Arch::X86 => "i686",
+        Arch::X86_64 => "x86_64",
+        Arch::Aarch64 => "aarch64",
+        Arch::Armv7L => "armv7l",
+        Arch::Ppc64 => "ppc64",
+        Arch::Ppc64Le => "ppc64le",
+        Arch::S390X => "s390x",
+    })
+}
+
+fn linux_platforms(arch: Arch) -> Vec<String> {
+    let Some(arch_str) = linux_arch_str(arch) else {
+        return vec![];
+    };
+    let mut tags = manylinux::expand_platform_tag(&format!(
+        "manylinux_2_{}_{}",
+        LATEST_MANYLINUX_MINOR, arch_str
+    ));
+    tags.extend(
+        (0..=LATEST_MUSLLINUX_MINOR)
+            .rev()
+            .map(|m| format!("musllinux_1_{}_{}", m, arch_str)),
+    );
+    tags.push(format!("linux_{}", arch_str));
+    tags
+}
+
+fn macos_platforms(arch: Arch) -> Vec<String> {
+    let arch_str = match arch {
+        Arch::X86_64 => "x86_64",
+        Arch::Aarch64 => "arm64",
+        _ => return vec![],
+    };
+    let mut tags = Vec::new();
+    for major in (11..=14).rev() {
+        tags.push(format!("macosx_{}_0_{}", major, arch_str));
+        tags.push(format!("macosx_{}_0_universal2", major));
+    }
+    if arch == Arch::X86_64 {
+        for minor in (6..=16).rev() {
+            tags.push(format!("macosx_10_{}_x86_64", minor));
+            tags.push(format!("macosx_10_{}_intel", minor));
+            tags.push(format!("macosx_10_{}_universal2", minor));
+        }
+    }
+    tags
+}
+
+fn windows_platforms(arch: Arch) -> Vec<String> {
+    match arch {
+        Arch::X86 => vec!["win32".to_string()],
+        Arch::X86_64 => vec!["win_amd64".to_string()],
+        Arch::Aarch64 => vec!["win_arm64".to_string()],
+        _ => vec![],
+    }
+}
+
+fn freebsd_platforms(arch: Arch) -> Vec<String> {
+    linux_arch_str(arch)
+        .map(|arch_str| vec![format!("freebsd_{}", arch_str)])
+        .unwrap_or_default()
+}
+
+pub fn platforms_for(os: Os, arch: Arch) -> Vec<String> {
+    match os {
+        Os::Linux => linux_platforms(arch),
+        Os::Macos => macos_platforms(arch),
+        Os::Windows => windows_platforms(arch),
+        Os::FreeBsd => freebsd_platforms(arch),
+    }
+}
+
 #[cfg_attr(feature = "pyo3", pyclass(get_all))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TargetPython {
@@ -87,11 +365,55 @@ impl TargetPython {
     pub fn new(supported_tags: Vec<Tag>) -> Self {
         Self { supported_tags }
     }
+
+    pub fn for_interpreter(
+        py_ver: PythonVersion,
+        abis: Option<Vec<String>>,
+        implementation: Option<&str>,
+        platforms: Vec<String>,
+    ) -> Self {
+        Self::new(native::get_supported_tags(py_ver, abis, implementation, platforms))
+    }
+
+    pub fn for_target(
+        py_ver: PythonVersion,
+        os: Os,
+        arch: Arch,
+        abis: Option<Vec<String>>,
+        implementation: Option<&str>,
+    ) -> Self {
+        Self::for_interpreter(py_ver, abis, implementation, platforms_for(os, arch))
+    }
+}
+
+fn default_native_platforms() -> Option<Vec<String>> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let arch = std::env::consts::ARCH;
+    let mut tags = manylinux::default_platform_tags(arch);
+    if tags.is_empty() {
+        return None;
+    }
+    tags.push(format!("linux_{}", arch));
+    Some(tags)
 }
 
 #[cfg(feature = "pyo3")]
 #[pymethods]
 impl TargetPython {
+    #[staticmethod]
+    #[pyo3(signature = (py_ver, os, arch, abis = None, implementation = None))]
+    fn for_foreign_target(
+        py_ver: PythonVersion,
+        os: Os,
+        arch: Arch,
+        abis: Option<Vec<String>>,
+        implementation: Option<String>,
+    ) -> Self {
+        Self::for_target(py_ver, os, arch, abis, implementation.as_deref())
+    }
+
     #[new]
     #[pyo3(signature = (py_ver = None, abis = None, implementation = None, platforms = None))]
     fn py_new(
@@ -100,6 +422,26 @@ impl TargetPython {
         implementation: Option<String>,
         platforms: Option<Vec<String>>,
     ) -> PyResult<Self> {
+        // Once the caller has already pinned down a version and a
+        // platform list there's nothing left to introspect, so generate
+        // the tags natively instead of round-tripping into
+        // `packaging.tags` for them. When no platforms were given, try a
+        // host glibc probe (also pure Rust) before falling back to
+        // `packaging.tags`, which is still needed to detect the running
+        // interpreter/platform on non-Linux hosts, and to expand aliased
+        // platform specs like `macosx_11_0_arm64`.
+        if let Some(py_ver) = py_ver {
+            let native_platforms = match py_impl::expand_allowed_platforms(platforms.clone())? {
+                Some(expanded) => Some(expanded),
+                None => default_native_platforms(),
+            };
+            if let Some(platforms) = native_platforms {
+                let tags =
+                    native::get_supported_tags(py_ver, abis, implementation.as_deref(), platforms);
+                return Ok(Self::new(tags));
+            }
+        }
+
         let tags = py_impl::get_supported_tags(py_ver, abis, implementation, platforms)?;
 
         Ok(Self::new(tags))
@@ -182,7 +524,9 @@ mod py_impl {
         ))
     }
 
-    fn expand_allowed_platforms(platforms: Option<Vec<String>>) -> PyResult<Option<Vec<String>>> {
+    pub(super) fn expand_allowed_platforms(
+        platforms: Option<Vec<String>>,
+    ) -> PyResult<Option<Vec<String>>> {
         let result = platforms.map(|values| {
             let mut seen = HashSet::new();
             let mut result = Vec::new();
@@ -210,6 +554,15 @@ mod py_impl {
             mac_platforms(arch)?
         } else if arch_prefix == "manylinux2014" || arch_prefix == "manylinux2010" {
             custom_manylinux_platforms(arch)
+        } else if arch_prefix == "manylinux" {
+            crate::manylinux::expand_platform_tag(arch)
+        } else if arch_prefix == "musllinux" {
+            match std::env::current_exe() {
+                Ok(interpreter_path) => {
+                    crate::musllinux::expand_platform_tag(arch, &interpreter_path)
+                }
+                Err(_) => vec![arch.to_string()],
+            }
         } else {
             vec![arch.to_string()]
         };
@@ -261,3 +614,63 @@ mod py_impl {
         arches
     }
 }
+
+#[cfg(test)]
+mod platform_tests {
+    use super::*;
+
+    #[test]
+    fn test_linux_platforms_orders_newest_first_and_ends_with_bare_linux() {
+        let tags = linux_platforms(Arch::X86_64);
+        assert_eq!(tags[0], format!("manylinux_2_{}_x86_64", LATEST_MANYLINUX_MINOR));
+        assert!(tags.contains(&"manylinux2014_x86_64".to_string()));
+        assert_eq!(
+            tags[tags.len() - LATEST_MUSLLINUX_MINOR as usize - 2],
+            format!("musllinux_1_{}_x86_64", LATEST_MUSLLINUX_MINOR)
+        );
+        assert_eq!(tags.last(), Some(&"linux_x86_64".to_string()));
+    }
+
+    #[test]
+    fn test_linux_platforms_nonempty_for_every_arch() {
+        assert!(!linux_platforms(Arch::X86).is_empty());
+        assert!(!linux_platforms(Arch::X86_64).is_empty());
+        assert!(!linux_platforms(Arch::Aarch64).is_empty());
+        assert!(!linux_platforms(Arch::Armv7L).is_empty());
+        assert!(!linux_platforms(Arch::Ppc64).is_empty());
+        assert!(!linux_platforms(Arch::Ppc64Le).is_empty());
+        assert!(!linux_platforms(Arch::S390X).is_empty());
+    }
+
+    #[test]
+    fn test_macos_platforms_x86_64_includes_universal2_and_intel_era() {
+        let tags = macos_platforms(Arch::X86_64);
+        assert!(tags.contains(&"macosx_14_0_universal2".to_string()));
+        assert!(tags.contains(&"macosx_10_6_intel".to_string()));
+        assert!(tags.contains(&"macosx_10_16_x86_64".to_string()));
+    }
+
+    #[test]
+    fn test_macos_platforms_aarch64_skips_intel_era() {
+        let tags = macos_platforms(Arch::Aarch64);
+        assert!(tags.contains(&"macosx_11_0_arm64".to_string()));
+        assert!(!tags.iter().any(|t| t.contains("10_6")));
+    }
+
+    #[test]
+    fn test_windows_platforms_maps_one_tag_per_arch() {
+        assert_eq!(windows_platforms(Arch::X86_64), vec!["win_amd64".to_string()]);
+        assert_eq!(windows_platforms(Arch::X86), vec!["win32".to_string()]);
+        assert_eq!(windows_platforms(Arch::Aarch64), vec!["win_arm64".to_string()]);
+        assert!(windows_platforms(Arch::Ppc64).is_empty());
+    }
+
+    #[test]
+    fn test_platforms_for_dispatches_by_os() {
+        assert_eq!(platforms_for(Os::Windows, Arch::X86_64), vec!["win_amd64".to_string()]);
+        assert_eq!(
+            platforms_for(Os::Linux, Arch::X86_64),
+            linux_platforms(Arch::X86_64)
+        );
+    }
+}
@@ -1,19 +1,65 @@
-use crate::{Error, ErrorKind};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::StatusCode;
+
+use crate::{
+    cache::Cache,
+    hash::Hasher,
+    http_cache::IndexCache,
+    link::{preferred_hash, Link},
+    source::ARCHIVE_EXTENSIONS,
+    Error, ErrorKind,
+};
+
+lazy_static! {
+    static ref CONTENT_DISPOSITION_FILENAME: Regex =
+        Regex::new(r#"(?i)filename\*?=\"?([^\";]+)\"?"#).unwrap();
+}
+
+const DEFAULT_MAX_CONCURRENCY: usize = 8;
 
 #[derive(Debug)]
 pub struct PyPISession {
-    client: reqwest::blocking::Client,
+    // Used for hosts not in `trusted_host_ports`.
+    strict_client: reqwest::blocking::Client,
+    // Permits plain `http://` and relaxed TLS for trusted hosts.
+    relaxed_client: reqwest::blocking::Client,
     trusted_host_ports: Vec<(String, Option<u16>)>,
+    max_concurrency: usize,
+    cache: Option<Cache>,
+    index_cache: Option<IndexCache>,
 }
 
 impl PyPISession {
     pub fn new() -> Self {
         Self {
-            client: reqwest::blocking::Client::new(),
+            strict_client: reqwest::blocking::Client::new(),
+            relaxed_client: reqwest::blocking::Client::builder()
+                .danger_accept_invalid_certs(true)
+                .build()
+                .expect("failed to build the relaxed TLS client"),
             trusted_host_ports: vec![],
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            cache: None,
+            index_cache: None,
         }
     }
 
+    pub fn with_cache(mut self, root: std::path::PathBuf) -> Result<Self, Error> {
+        self.cache = Some(Cache::new(root)?);
+        Ok(self)
+    }
+
+    pub fn with_index_cache(mut self, root: std::path::PathBuf) -> Result<Self, Error> {
+        self.index_cache = Some(IndexCache::new(root)?);
+        Ok(self)
+    }
+
     pub fn add_trusted_host(&mut self, host: &str) -> Result<(), Error> {
         let url = build_url_from_netloc(host)?;
         self.trusted_host_ports
@@ -21,29 +67,263 @@ impl PyPISession {
         Ok(())
     }
 
+    pub fn set_max_concurrency(&mut self, max_concurrency: usize) {
+        self.max_concurrency = max_concurrency;
+    }
+
+    pub fn prune_cache(&self) -> Result<usize, Error> {
+        match &self.cache {
+            Some(cache) => cache.prune(),
+            None => Ok(0),
+        }
+    }
+
+    pub(crate) fn cache(&self) -> Option<&Cache> {
+        self.cache.as_ref()
+    }
+
+    pub(crate) fn index_cache(&self) -> Option<&IndexCache> {
+        self.index_cache.as_ref()
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn download_all(&self, links: &[Link], dest: &Path) -> Vec<Result<PathBuf, Error>> {
+        use rayon::prelude::*;
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.max_concurrency)
+            .build()
+            .expect("failed to build download thread pool");
+        pool.install(|| {
+            links
+                .par_iter()
+                .map(|link| self.download_one(link, dest))
+                .collect()
+        })
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    pub fn download_all(&self, links: &[Link], dest: &Path) -> Vec<Result<PathBuf, Error>> {
+        links.iter().map(|link| self.download_one(link, dest)).collect()
+    }
+
+    fn download_one(&self, link: &Link, dest: &Path) -> Result<PathBuf, Error> {
+        let path = dest.join(link.filename());
+        if let Some(cache) = &self.cache {
+            if let Some(cached) = cache.get(link) {
+                std::fs::copy(&cached, &path)?;
+                return Ok(path);
+            }
+        }
+        let resp = self.get(&link.normalized).send()?;
+        crate::source::check_for_status(&resp)?;
+        let bytes = resp.bytes()?;
+        verify_hash(link, &bytes)?;
+        std::fs::write(&path, &bytes)?;
+        if let Some(cache) = &self.cache {
+            cache.put(link, &bytes)?;
+        }
+        Ok(path)
+    }
+
+    pub fn download_verified(&self, link: &Link, dest: &Path) -> Result<PathBuf, Error> {
+        let expected = link.hashes().and_then(|hashes| preferred_hash(&hashes));
+        let mut hasher = match &expected {
+            Some((algo, _)) => Some(Hasher::new(algo).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::LinkMismatchError,
+                    format!("Unsupported hash algo {}", algo),
+                )
+            })?),
+            None => None,
+        };
+
+        let path = dest.join(link.filename());
+        let mut resp = self.get(&link.normalized).send()?;
+        crate::source::check_for_status(&resp)?;
+        let mut file = File::create(&path)?;
+        let mut buffer = [0; 1024 * 8];
+        loop {
+            let bytes_read = resp.read(&mut buffer)?;
+            if bytes_read == 0 {
+                break;
+            }
+            if let Some(hasher) = hasher.as_mut() {
+                hasher.update(&buffer[..bytes_read]);
+            }
+            file.write_all(&buffer[..bytes_read])?;
+        }
+        drop(file);
+
+        if let (Some(hasher), Some((algo, expected))) = (hasher, expected) {
+            let actual = hasher.hexdigest();
+            if actual != expected {
+                let _ = std::fs::remove_file(&path);
+                return Err(Error::new(
+                    ErrorKind::LinkMismatchError,
+                    format!(
+                        "Hash mismatch for {}: expected {} {}, actual {}",
+                        link, algo, expected, actual
+                    ),
+                ));
+            }
+        }
+        Ok(path)
+    }
+
+    pub fn resolve(&self, link: &Link) -> Result<Link, Error> {
+        let mut resp = self.head(&link.normalized).send()?;
+        if !resp.status().is_success() {
+            // Some servers don't implement HEAD; fall back to a ranged GET.
+            resp = self
+                .get(&link.normalized)
+                .header("Range", "bytes=0-0")
+                .send()?;
+        }
+
+        let status = resp.status();
+        if status == StatusCode::NOT_FOUND || status == StatusCode::GONE {
+            return Err(Error::new(
+                ErrorKind::CollectError,
+                format!("{} is no longer available ({})", link, status),
+            ));
+        }
+        if !status.is_success() && !status.is_redirection() {
+            return Err(Error::new(
+                ErrorKind::CollectError,
+                format!("{} returned {}", link, status),
+            ));
+        }
+
+        let final_url = resp.url().to_string();
+        let content_type = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        let disposition_filename = content_disposition_filename(&resp);
+        let filename = disposition_filename
+            .clone()
+            .or_else(|| Link::from_str(&final_url).ok().map(|l| l.filename()))
+            .unwrap_or_default();
+        if !looks_like_distribution(&filename, content_type.as_deref()) {
+            return Err(Error::new(
+                ErrorKind::CollectError,
+                format!("{} does not look like a distribution file", final_url),
+            ));
+        }
+
+        Ok(Link::new(
+            final_url,
+            link.comes_from.clone(),
+            link.yank_reason.clone(),
+            link.requires_python.clone(),
+            link.hashes(),
+            link.dist_metadata.clone(),
+        )?
+        .with_filename_override(disposition_filename))
+    }
+
     pub fn get(&self, url: &str) -> reqwest::blocking::RequestBuilder {
-        self.client.get(url)
+        self.client_for(url).get(url)
     }
 
     pub fn head(&self, url: &str) -> reqwest::blocking::RequestBuilder {
-        self.client.head(url)
+        self.client_for(url).head(url)
     }
 
     pub fn post(&self, url: &str) -> reqwest::blocking::RequestBuilder {
-        self.client.post(url)
+        self.client_for(url).post(url)
     }
 
     pub fn put(&self, url: &str) -> reqwest::blocking::RequestBuilder {
-        self.client.put(url)
+        self.client_for(url).put(url)
     }
 
     pub fn patch(&self, url: &str) -> reqwest::blocking::RequestBuilder {
-        self.client.patch(url)
+        self.client_for(url).patch(url)
     }
 
     pub fn delete(&self, url: &str) -> reqwest::blocking::RequestBuilder {
-        self.client.delete(url)
+        self.client_for(url).delete(url)
+    }
+
+    fn client_for(&self, url: &str) -> &reqwest::blocking::Client {
+        let trusted = url::Url::parse(url).map_or(false, |parsed| {
+            is_trusted_host(
+                &self.trusted_host_ports,
+                parsed.host_str().unwrap_or_default(),
+                parsed.port_or_known_default(),
+            )
+        });
+        if trusted {
+            &self.relaxed_client
+        } else {
+            &self.strict_client
+        }
+    }
+}
+
+/// Match `(host, port)` against trusted entries; a `None` port matches any port.
+pub(crate) fn is_trusted_host(
+    trusted: &[(String, Option<u16>)],
+    host: &str,
+    port: Option<u16>,
+) -> bool {
+    trusted.iter().any(|(trusted_host, trusted_port)| {
+        trusted_host.eq_ignore_ascii_case(host) && (trusted_port.is_none() || *trusted_port == port)
+    })
+}
+
+fn verify_hash(link: &Link, bytes: &[u8]) -> Result<(), Error> {
+    let hashes = match link.hashes() {
+        Some(hashes) => hashes,
+        None => return Ok(()),
+    };
+    let (algo, expected) = match preferred_hash(&hashes) {
+        Some(pair) => pair,
+        None => return Ok(()),
+    };
+    let mut hasher = Hasher::new(&algo).ok_or_else(|| {
+        Error::new(ErrorKind::LinkMismatchError, format!("Unsupported hash algo {}", algo))
+    })?;
+    hasher.update(bytes);
+    let actual = hasher.hexdigest();
+    if actual != expected {
+        return Err(Error::new(
+            ErrorKind::LinkMismatchError,
+            format!(
+                "Hash mismatch for {}: expected {} {}, actual {}",
+                link, algo, expected, actual
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn content_disposition_filename(resp: &reqwest::blocking::Response) -> Option<String> {
+    let value = resp
+        .headers()
+        .get(reqwest::header::CONTENT_DISPOSITION)?
+        .to_str()
+        .ok()?;
+    CONTENT_DISPOSITION_FILENAME
+        .captures(value)
+        .map(|caps| caps[1].trim().to_string())
+}
+
+fn looks_like_distribution(filename: &str, content_type: Option<&str>) -> bool {
+    if filename.ends_with(".whl") || ARCHIVE_EXTENSIONS.iter().any(|ext| filename.ends_with(ext)) {
+        return true;
     }
+    matches!(
+        content_type,
+        Some("application/zip")
+            | Some("application/x-tar")
+            | Some("application/gzip")
+            | Some("application/x-gzip")
+            | Some("application/octet-stream")
+    )
 }
 
 fn build_url_from_netloc(netloc: &str) -> Result<url::Url, Error> {
@@ -58,3 +338,31 @@ fn build_url_from_netloc(netloc: &str) -> Result<url::Url, Error> {
     url::Url::parse(&full_url)
         .map_err(|_| Error::new(ErrorKind::ValueError, format!("Invalid host: {netloc}")))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_trusted_host_matches_any_port() {
+        let trusted = vec![("example.com".to_string(), None)];
+        assert!(is_trusted_host(&trusted, "example.com", Some(8080)));
+        assert!(is_trusted_host(&trusted, "EXAMPLE.COM", None));
+        assert!(!is_trusted_host(&trusted, "other.com", None));
+    }
+
+    #[test]
+    fn test_is_trusted_host_matches_specific_port_only() {
+        let trusted = vec![("example.com".to_string(), Some(8443))];
+        assert!(is_trusted_host(&trusted, "example.com", Some(8443)));
+        assert!(!is_trusted_host(&trusted, "example.com", Some(443)));
+    }
+
+    #[test]
+    fn test_is_trusted_host_ipv6() {
+        let url = build_url_from_netloc("[::1]:8080").unwrap();
+        let trusted = vec![(url.host_str().unwrap().to_string(), url.port())];
+        assert!(is_trusted_host(&trusted, "[::1]", Some(8080)));
+        assert!(!is_trusted_host(&trusted, "[::1]", Some(9090)));
+    }
+}
@@ -1,11 +1,15 @@
 use crate::error::ErrorKind;
+use crate::http_cache::parse_max_age;
 use crate::session::PyPISession;
 use crate::{error::Error, link::Link};
+use flate2::read::{GzDecoder, ZlibDecoder};
 use lazy_static::lazy_static;
 use mime_guess;
+use reqwest::StatusCode;
 use scraper::{Html, Selector};
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use url::Url;
@@ -65,20 +69,7 @@ pub fn collect_links(
         let path = PathBuf::from(source.file_path().unwrap());
         if path.is_dir() {
             if expand {
-                for entry in path.read_dir()? {
-                    let subpath = entry?.path();
-                    let file_url = Url::from_file_path(subpath)
-                        .map_err(|_| {
-                            Error::new(ErrorKind::IOError, "Invalid file URL".to_string())
-                        })?
-                        .to_string();
-                    let file_link = Link::from_str(file_url.as_str())?;
-                    if is_html_file(file_url.as_str()) {
-                        collected.extend(collect_links_from_page(client, &file_link)?);
-                    } else {
-                        collected.push(file_link);
-                    }
-                }
+                collected.extend(expand_directory(client, &path)?);
             } else {
                 let index = path.join("index.html");
                 let file_url = Url::from_file_path(index)
@@ -101,6 +92,47 @@ pub fn collect_links(
     Ok(collected)
 }
 
+#[cfg(feature = "parallel")]
+fn expand_directory(client: &PyPISession, path: &Path) -> Result<Vec<Link>, Error> {
+    use rayon::iter::{ParallelBridge, ParallelIterator};
+
+    let nested: Result<Vec<Vec<Link>>, Error> = path
+        .read_dir()?
+        .par_bridge()
+        .map(|entry| -> Result<Vec<Link>, Error> {
+            let subpath = entry?.path();
+            let file_url = Url::from_file_path(subpath)
+                .map_err(|_| Error::new(ErrorKind::IOError, "Invalid file URL".to_string()))?
+                .to_string();
+            let file_link = Link::from_str(file_url.as_str())?;
+            if is_html_file(file_url.as_str()) {
+                collect_links_from_page(client, &file_link)
+            } else {
+                Ok(vec![file_link])
+            }
+        })
+        .collect();
+    Ok(nested?.into_iter().flatten().collect())
+}
+
+#[cfg(not(feature = "parallel"))]
+fn expand_directory(client: &PyPISession, path: &Path) -> Result<Vec<Link>, Error> {
+    let mut collected = vec![];
+    for entry in path.read_dir()? {
+        let subpath = entry?.path();
+        let file_url = Url::from_file_path(subpath)
+            .map_err(|_| Error::new(ErrorKind::IOError, "Invalid file URL".to_string()))?
+            .to_string();
+        let file_link = Link::from_str(file_url.as_str())?;
+        if is_html_file(file_url.as_str()) {
+            collected.extend(collect_links_from_page(client, &file_link)?);
+        } else {
+            collected.push(file_link);
+        }
+    }
+    Ok(collected)
+}
+
 fn is_html_file(file_url: &str) -> bool {
     let mime_type = mime_guess::from_path(file_url).first_or_octet_stream();
     mime_type == mime_guess::mime::TEXT_HTML
@@ -131,26 +163,118 @@ fn get_pypi_response(client: &PyPISession, source: &Link) -> Result<PyPIResponse
         ensure_index_response(client, source)?;
     }
 
+    let url = source.normalized.as_str();
+    let index_cache = client.index_cache();
+    let cached = index_cache.and_then(|cache| cache.get(url));
+    if let Some(cached) = &cached {
+        if cached.fresh {
+            log::debug!("Using cached index response for {}", source);
+            return response_from_body(&cached.content_type, &cached.body);
+        }
+    }
+
     let accept_header = "application/vnd.pypi.simple.v1+json, \
         application/vnd.pypi.simple.v1+html; q=0.1, \
         text/html; q=0.01";
-    let response = client
-        .get(source.normalized.as_str())
+    let mut request = client
+        .get(url)
         .header("Accept", accept_header)
-        .header("Cache-Control", "max-age=0")
-        .send()?;
+        .header("Accept-Encoding", "gzip, br, deflate")
+        .header("Cache-Control", "max-age=0");
+    if let Some(cached) = &cached {
+        if let Some(etag) = &cached.etag {
+            request = request.header("If-None-Match", etag.clone());
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header("If-Modified-Since", last_modified.clone());
+        }
+    }
+    let response = request.send()?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        let cached = cached.ok_or_else(|| {
+            Error::new(
+                ErrorKind::CollectError,
+                format!("{} returned 304 with nothing cached to revalidate", source),
+            )
+        })?;
+        if let Some(cache) = index_cache {
+            cache.touch(url)?;
+        }
+        log::debug!("{} not modified, reusing cached response", source);
+        return response_from_body(&cached.content_type, &cached.body);
+    }
 
     check_for_status(&response)?;
 
-    match response.headers().get("Content-Type").map(|v| v.to_str()) {
-        Some(Ok("text/html")) | Some(Ok("application/vnd.pypi.simple.v1+html")) => {
-            Ok(PyPIResponse::Html(response.text()?))
+    let content_type = response
+        .headers()
+        .get("Content-Type")
+        .map(|v| v.to_str().map(|s| s.to_string()));
+    let content_encoding = response
+        .headers()
+        .get(reqwest::header::CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_ascii_lowercase());
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    let last_modified = response
+        .headers()
+        .get(reqwest::header::LAST_MODIFIED)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    let max_age = response
+        .headers()
+        .get(reqwest::header::CACHE_CONTROL)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_max_age);
+
+    let content_type = match content_type {
+        Some(Ok(value)) => value,
+        Some(Err(_)) => {
+            return Err(Error::new(
+                ErrorKind::CollectError,
+                "Invalid Content-Type header".to_string(),
+            ))
+        }
+        None => {
+            return Err(Error::new(
+                ErrorKind::CollectError,
+                "Unsupported Content-Type header".to_string(),
+            ))
         }
-        Some(Ok("application/vnd.pypi.simple.v1+json")) => Ok(PyPIResponse::Json(response.json()?)),
-        Some(Err(_)) => Err(Error::new(
+    };
+    if !matches!(
+        content_type.as_str(),
+        "text/html" | "application/vnd.pypi.simple.v1+html" | "application/vnd.pypi.simple.v1+json"
+    ) {
+        return Err(Error::new(
             ErrorKind::CollectError,
-            "Invalid Content-Type header".to_string(),
-        )),
+            "Unsupported Content-Type header".to_string(),
+        ));
+    }
+
+    let body = decode_body(response, content_encoding.as_deref())?;
+    if let Some(cache) = index_cache {
+        cache.store(url, &content_type, etag, last_modified, max_age, &body)?;
+    }
+    response_from_body(&content_type, &body)
+}
+
+fn response_from_body(content_type: &str, body: &[u8]) -> Result<PyPIResponse, Error> {
+    match content_type {
+        "text/html" | "application/vnd.pypi.simple.v1+html" => {
+            Ok(PyPIResponse::Html(String::from_utf8_lossy(body).into_owned()))
+        }
+        "application/vnd.pypi.simple.v1+json" => {
+            let json: Response = serde_json::from_slice(body).map_err(|e| {
+                Error::new(ErrorKind::CollectError, format!("Invalid JSON response: {}", e))
+            })?;
+            Ok(PyPIResponse::Json(json))
+        }
         _ => Err(Error::new(
             ErrorKind::CollectError,
             "Unsupported Content-Type header".to_string(),
@@ -158,6 +282,27 @@ fn get_pypi_response(client: &PyPISession, source: &Link) -> Result<PyPIResponse
     }
 }
 
+fn decode_body(
+    response: reqwest::blocking::Response,
+    content_encoding: Option<&str>,
+) -> Result<Vec<u8>, Error> {
+    let bytes = response.bytes()?;
+    let mut decoded = Vec::new();
+    match content_encoding {
+        Some("gzip") | Some("x-gzip") => {
+            GzDecoder::new(&bytes[..]).read_to_end(&mut decoded)?;
+        }
+        Some("br") => {
+            brotli::Decompressor::new(&bytes[..], 4096).read_to_end(&mut decoded)?;
+        }
+        Some("deflate") => {
+            ZlibDecoder::new(&bytes[..]).read_to_end(&mut decoded)?;
+        }
+        _ => decoded.extend_from_slice(&bytes),
+    }
+    Ok(decoded)
+}
+
 fn parse_links_from_html(html: String, from_url: &str) -> Result<Vec<Link>, Error> {
     let base_url = Url::parse(from_url).unwrap();
     let document = Html::parse_document(html.as_str());
@@ -244,7 +389,7 @@ fn ensure_index_response(client: &PyPISession, source: &Link) -> Result<(), Erro
     check_for_status(&resp)
 }
 
-fn check_for_status(resp: &reqwest::blocking::Response) -> Result<(), Error> {
+pub(crate) fn check_for_status(resp: &reqwest::blocking::Response) -> Result<(), Error> {
     let reason = resp
         .status()
         .canonical_reason()